@@ -1,21 +1,62 @@
-use std::ffi::{CStr, CString, c_char};
+use core::ffi::{CStr, c_char};
+use core::fmt::{self, Write as _};
 
-use arrayvec::ArrayString;
+#[cfg(feature = "alloc")]
+use alloc::{borrow::ToOwned, ffi::CString, string::String, vec::Vec};
 
-use crate::{CStrError, ContainsNulError};
+use arrayvec::ArrayVec;
 
-#[derive(Debug, Clone, Eq, Ord, PartialEq, PartialOrd, Hash)]
+use crate::{CStrError, CapacityExceededError, ContainsNulError};
+
+#[derive(Clone, Eq, Ord, PartialEq, PartialOrd, Hash)]
 pub enum CArrayString<const N: usize> {
-    Stack(ArrayString<N>),
+    Stack(ArrayVec<u8, N>),
+    #[cfg(feature = "alloc")]
     Heap(CString),
 }
 
+/// Writes a byte sequence the way the Linux kernel's `BStr` does: printable
+/// ASCII is copied verbatim, `\t`/`\n`/`\r` use their usual escapes, and any
+/// other byte outside `0x20..=0x7e` is rendered as `\xNN`.
+///
+/// This lets a [`CArrayString`] be logged safely even when it holds
+/// non-UTF-8 bytes (paths, locale-encoded data, ...).
+fn write_escaped(bytes: &[u8], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for &b in bytes {
+        match b {
+            b'\t' => f.write_str("\\t")?,
+            b'\n' => f.write_str("\\n")?,
+            b'\r' => f.write_str("\\r")?,
+            0x20..=0x7e => f.write_char(b as char)?,
+            _ => write!(f, "\\x{b:02x}")?,
+        }
+    }
+    Ok(())
+}
+
+impl<const N: usize> fmt::Display for CArrayString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_escaped(self.as_c_str().to_bytes(), f)
+    }
+}
+
+impl<const N: usize> fmt::Debug for CArrayString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char('"')?;
+        write_escaped(self.as_c_str().to_bytes(), f)?;
+        f.write_char('"')
+    }
+}
+
+#[cfg(feature = "alloc")]
 impl<const N: usize> From<&CStr> for CArrayString<N> {
     fn from(value: &CStr) -> Self {
-        if value.count_bytes() < N {
-            let mut buf = ArrayString::<N>::new();
-            buf.push_str(unsafe { str::from_utf8_unchecked(value.to_bytes()) });
-            buf.push('\0');
+        let bytes = value.to_bytes();
+        if bytes.len() < N {
+            let mut buf = ArrayVec::<u8, N>::new();
+            buf.try_extend_from_slice(bytes)
+                .expect("length already checked against N");
+            buf.push(0);
             Self::Stack(buf)
         } else {
             Self::Heap(value.to_owned())
@@ -27,18 +68,18 @@ impl<const N: usize> TryFrom<&[u8]> for CArrayString<N> {
     type Error = CStrError;
 
     fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
-        CStr::from_bytes_with_nul(value)
-            .map(CArrayString::from)
-            .map_err(Into::into)
+        Self::try_from_bytes_with_nul(value)
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<const N: usize> From<&CString> for CArrayString<N> {
     fn from(value: &CString) -> Self {
-        From::<&CStr>::from(value)
+        From::<&CStr>::from(value.as_c_str())
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<const N: usize> From<CString> for CArrayString<N> {
     fn from(value: CString) -> Self {
         Self::Heap(value)
@@ -54,23 +95,63 @@ impl<const N: usize> TryFrom<&str> for CArrayString<N> {
             match core::slice::memchr::memchr(0, bytes) {
                 Some(_i) => Err(Into::into(ContainsNulError)),
                 None => Ok({
-                    let mut buf = ArrayString::<N>::new();
-                    buf.push_str(value);
-                    buf.push('\0');
+                    let mut buf = ArrayVec::<u8, N>::new();
+                    buf.try_extend_from_slice(bytes)
+                        .expect("length already checked against N");
+                    buf.push(0);
                     Self::Stack(buf)
                 }),
             }
         } else {
-            CString::new(value).map(Self::Heap).map_err(Into::into)
+            Self::heap_from_parts(&[], value.as_bytes())
         }
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<const N: usize> TryFrom<&String> for CArrayString<N> {
     type Error = CStrError;
 
     fn try_from(value: &String) -> Result<Self, Self::Error> {
-        TryFrom::<&str>::try_from(value)
+        TryFrom::<&str>::try_from(value.as_str())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize> TryFrom<String> for CArrayString<N> {
+    type Error = CStrError;
+
+    /// Consumes `value` without an extra copy: when it is too large for the
+    /// stack buffer, [`CString::new`] takes ownership of the `String`'s existing
+    /// allocation and appends the NUL terminator to it rather than copying the
+    /// contents into a new one. That allocation is still resized (grown to hold
+    /// the NUL, or shrunk to fit) unless its capacity is exactly one byte more
+    /// than its length.
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value.len() < N {
+            TryFrom::<&str>::try_from(value.as_str())
+        } else {
+            CString::new(value).map(Self::Heap).map_err(Into::into)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize> TryFrom<Vec<u8>> for CArrayString<N> {
+    type Error = CStrError;
+
+    /// Consumes `value` without an extra copy: when it is too large for the
+    /// stack buffer, [`CString::new`] takes ownership of the `Vec`'s existing
+    /// allocation and appends the NUL terminator to it rather than copying the
+    /// contents into a new one. That allocation is still resized (grown to hold
+    /// the NUL, or shrunk to fit) unless its capacity is exactly one byte more
+    /// than its length.
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        if value.len() < N {
+            Self::from_bytes(&value)
+        } else {
+            CString::new(value).map(Self::Heap).map_err(Into::into)
+        }
     }
 }
 
@@ -78,8 +159,10 @@ impl<const N: usize> TryFrom<&String> for CArrayString<N> {
 ///
 /// `CArrayString<N>` provides a unified abstraction over two storage strategies:
 ///
-/// 1. **Stack-allocated:** Uses [`ArrayString<N>`] for small strings that fit into
-///    a fixed-size buffer. This avoids heap allocation and is very efficient.
+/// 1. **Stack-allocated:** Uses a fixed-size `[u8; N]` buffer (via [`ArrayVec<u8, N>`])
+///    for small strings that fit into it. Because the buffer is byte-oriented rather
+///    than UTF-8-checked, it can faithfully hold any null-free byte sequence, including
+///    non-UTF-8 data such as paths or locale-encoded text.
 /// 2. **Heap-allocated:** Uses [`CString`] when the string exceeds the stack buffer,
 ///    ensuring the string is always valid and null-terminated.
 ///
@@ -94,7 +177,7 @@ impl<const N: usize> TryFrom<&String> for CArrayString<N> {
 ///
 /// ```text
 /// ┌───────────────┐
-/// │ Stack Buffer  │  (ArrayString<N>)
+/// │ Stack Buffer  │  (ArrayVec<u8, N>)
 /// └───────────────┘
 ///       │ fits
 ///       └─> use stack
@@ -109,11 +192,27 @@ impl<const N: usize> TryFrom<&String> for CArrayString<N> {
 /// - Large strings trigger heap allocation, which may be slower and use more memory.
 /// - Prefer choosing `N` large enough for your common use case to minimize heap fallbacks.
 ///
+/// # Mutable Builder
+///
+/// [`push_str`]/[`try_push_str`] (and the [`fmt::Write`] impl) let you grow
+/// a `CArrayString` incrementally. A `Stack` value is promoted to `Heap` the
+/// moment an append would overflow `N`, the same way `smallvec` spills a small
+/// vector onto the heap.
+///
+/// # `no_std`
+///
+/// The heap fallback above requires the `alloc` feature (on by default).
+/// Without it, only the strictly stack-bound APIs are available: constructors
+/// return a [`CStrError`] instead of allocating when the content would not
+/// fit in `N` bytes, and [`try_new_stack`] is always available regardless of
+/// the feature.
+///
 /// # Examples
 ///
 /// ```
+/// # #[cfg(feature = "alloc")] {
 /// use std::ffi::CStr;
-/// 
+///
 /// use stack_cstr::CArrayString;
 ///
 /// // Small string fits on stack
@@ -133,13 +232,62 @@ impl<const N: usize> TryFrom<&String> for CArrayString<N> {
 /// unsafe {
 ///     assert_eq!(CStr::from_ptr(ptr).to_str().unwrap(), "hello");
 /// }
+/// # }
 /// ```
 impl<const N: usize> CArrayString<N> {
+    /// Writes the stack-buffer part of `new`/`try_new_stack`: formats `fmt`
+    /// straight into an `N`-byte `ArrayVec`, one `&str` chunk at a time,
+    /// rejects output that contains an interior NUL byte, and NUL-terminates
+    /// it. Used by both, so neither duplicates the other.
+    fn try_stack(fmt: fmt::Arguments) -> Result<ArrayVec<u8, N>, CStrError> {
+        /// Adapts [`fmt::Write`] (which only accepts `&str`) onto the
+        /// byte-oriented stack buffer, since formatted text is always valid UTF-8.
+        struct ArrayVecWriter<'a, const N: usize>(&'a mut ArrayVec<u8, N>);
+
+        impl<const N: usize> fmt::Write for ArrayVecWriter<'_, N> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                self.0
+                    .try_extend_from_slice(s.as_bytes())
+                    .map_err(|_| fmt::Error)
+            }
+        }
+
+        let mut buf = ArrayVec::<u8, N>::new();
+        // `ArrayVecWriter::write_str` only ever fails due to capacity, so any
+        // error here is a capacity error, not a genuine formatting failure.
+        fmt::write(&mut ArrayVecWriter(&mut buf), fmt).map_err(|_| CapacityExceededError)?;
+        if core::slice::memchr::memchr(0, &buf).is_some() {
+            return Err(ContainsNulError.into());
+        }
+        buf.try_push(0).map_err(|_| CapacityExceededError)?;
+        Ok(buf)
+    }
+
+    /// Builds a `Heap` value out of an existing prefix plus a newly appended
+    /// suffix, without the `alloc` feature this is the graceful-degradation
+    /// point: there is no heap to spill into, so it reports
+    /// [`CapacityExceededError`] instead.
+    #[cfg(feature = "alloc")]
+    fn heap_from_parts(prefix: &[u8], suffix: &[u8]) -> Result<Self, CStrError> {
+        let mut owned = Vec::with_capacity(prefix.len() + suffix.len());
+        owned.extend_from_slice(prefix);
+        owned.extend_from_slice(suffix);
+        Ok(Self::Heap(CString::new(owned)?))
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    fn heap_from_parts(_prefix: &[u8], _suffix: &[u8]) -> Result<Self, CStrError> {
+        Err(CapacityExceededError.into())
+    }
+
     /// Creates a new C-compatible string using `format_args!`.
     ///
     /// Attempts to store the formatted string in a stack buffer of size `N`.
     /// Falls back to a heap allocation if the string does not fit.
     ///
+    /// Requires the `alloc` feature; use [`try_new_stack`] for a strict,
+    /// allocation-free alternative.
+    ///
     /// # Parameters
     ///
     /// - `fmt`: The formatted arguments, typically produced by `format_args!`.
@@ -160,22 +308,98 @@ impl<const N: usize> CArrayString<N> {
     /// let s = CArrayString::<8>::new(format_args!("hi {}!", "you"));
     /// assert!(s.as_c_str().to_str().unwrap().starts_with("hi"));
     /// ```
-    pub fn new(fmt: std::fmt::Arguments) -> CArrayString<N> {
-        fn try_stack<const N: usize>(
-            fmt: std::fmt::Arguments,
-        ) -> Result<ArrayString<N>, CStrError> {
-            let mut buf: ArrayString<N> = ArrayString::new();
-            std::fmt::write(&mut buf, fmt)?;
-            buf.try_push('\0')?;
-            Ok(buf)
+    #[cfg(feature = "alloc")]
+    pub fn new(fmt: fmt::Arguments) -> CArrayString<N> {
+        match Self::try_stack(fmt) {
+            Ok(arr) => Self::Stack(arr),
+            Err(_) => Self::Heap(CString::new(alloc::format!("{fmt}")).unwrap()),
         }
+    }
 
-        match try_stack::<N>(fmt) {
-            Ok(arr) => Self::Stack(arr),
-            Err(_) => Self::Heap(CString::new(std::fmt::format(fmt)).unwrap()),
+    /// Creates a new C-compatible string strictly on the stack, never allocating.
+    ///
+    /// This is the `no_std`-friendly counterpart of [`new`]: instead of
+    /// silently falling back to the heap, it returns `Err` when the formatted
+    /// string (plus NUL) does not fit in the `N`-byte stack buffer. It is
+    /// available regardless of the `alloc` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the formatted string does not fit in `N` bytes, or
+    /// if it contains an interior NUL byte.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stack_cstr::CArrayString;
+    ///
+    /// let s = CArrayString::<8>::try_new_stack(format_args!("hi")).unwrap();
+    /// assert_eq!(s.as_c_str().to_str().unwrap(), "hi");
+    ///
+    /// assert!(CArrayString::<4>::try_new_stack(format_args!("too long")).is_err());
+    /// ```
+    pub fn try_new_stack(fmt: fmt::Arguments) -> Result<Self, CStrError> {
+        Self::try_stack(fmt).map(Self::Stack)
+    }
+
+    /// Creates a `CArrayString` from a raw, NUL-free byte sequence.
+    ///
+    /// Unlike [`TryFrom<&str>`], this accepts arbitrary non-UTF-8 bytes (e.g. a
+    /// locale-encoded path), so it can faithfully round-trip C strings that are
+    /// not valid UTF-8. A trailing NUL is appended automatically.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `value` contains an interior NUL byte, or (without
+    /// the `alloc` feature) if it does not fit in the `N`-byte stack buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stack_cstr::CArrayString;
+    ///
+    /// let s = CArrayString::<8>::from_bytes(b"\xffoo").unwrap();
+    /// assert_eq!(s.as_c_str().to_bytes(), b"\xffoo");
+    /// ```
+    pub fn from_bytes(value: &[u8]) -> Result<Self, CStrError> {
+        if core::slice::memchr::memchr(0, value).is_some() {
+            return Err(ContainsNulError.into());
+        }
+
+        if value.len() < N {
+            let mut buf = ArrayVec::<u8, N>::new();
+            buf.try_extend_from_slice(value)
+                .expect("length already checked against N");
+            buf.push(0);
+            Ok(Self::Stack(buf))
+        } else {
+            Self::heap_from_parts(&[], value)
         }
     }
 
+    /// Creates a `CArrayString` from bytes that already carry a single trailing NUL.
+    ///
+    /// This is the byte-oriented counterpart of [`CStr::from_bytes_with_nul`]: it
+    /// rejects interior NULs but, unlike going through `&str`, accepts non-UTF-8
+    /// content.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `value` is not exactly one NUL-terminated, NUL-free string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stack_cstr::CArrayString;
+    ///
+    /// let s = CArrayString::<8>::try_from_bytes_with_nul(b"\xffoo\0").unwrap();
+    /// assert_eq!(s.as_c_str().to_bytes(), b"\xffoo");
+    /// ```
+    pub fn try_from_bytes_with_nul(value: &[u8]) -> Result<Self, CStrError> {
+        let cstr = CStr::from_bytes_with_nul(value)?;
+        Self::from_bytes(cstr.to_bytes())
+    }
+
     /// Returns a raw pointer to the null-terminated C string.
     ///
     /// The pointer is valid for the lifetime of `self`.
@@ -185,10 +409,10 @@ impl<const N: usize> CArrayString<N> {
     ///
     /// ```
     /// use std::ffi::CStr;
-    /// 
+    ///
     /// use stack_cstr::CArrayString;
     ///
-    /// let s = CArrayString::<8>::new(format_args!("hello"));
+    /// let s = CArrayString::<8>::try_from("hello").unwrap();
     /// let ptr = s.as_ptr();
     /// unsafe {
     ///     assert_eq!(CStr::from_ptr(ptr).to_str().unwrap(), "hello");
@@ -197,6 +421,7 @@ impl<const N: usize> CArrayString<N> {
     pub fn as_ptr(&self) -> *const c_char {
         match self {
             CArrayString::Stack(s) => s.as_ptr() as _,
+            #[cfg(feature = "alloc")]
             CArrayString::Heap(s) => s.as_ptr(),
         }
     }
@@ -210,19 +435,104 @@ impl<const N: usize> CArrayString<N> {
     ///
     /// ```
     /// use std::ffi::CStr;
-    /// 
+    ///
     /// use stack_cstr::CArrayString;
     ///
-    /// let s = CArrayString::<8>::new(format_args!("hello"));
+    /// let s = CArrayString::<8>::try_from("hello").unwrap();
     /// let cstr: &CStr = s.as_c_str();
     /// assert_eq!(cstr.to_str().unwrap(), "hello");
     /// ```
     pub fn as_c_str(&self) -> &CStr {
         match self {
-            CArrayString::Stack(s) => unsafe { CStr::from_bytes_with_nul_unchecked(s.as_bytes()) },
+            CArrayString::Stack(s) => unsafe { CStr::from_bytes_with_nul_unchecked(s.as_slice()) },
+            #[cfg(feature = "alloc")]
             CArrayString::Heap(s) => s.as_c_str(),
         }
     }
+
+    /// Appends `s`, promoting a `Stack` value to `Heap` if it no longer fits.
+    ///
+    /// The NUL terminator is kept valid after the append: a `Stack` buffer that
+    /// still has room is rewritten in place, while one that would overflow is
+    /// spilled (smallvec-style) into a `CString` holding the existing contents
+    /// followed by `s`. A `Heap` value is always appended to directly.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `s` contains an interior NUL byte, or (without the
+    /// `alloc` feature) if the append would overflow the `N`-byte stack buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stack_cstr::CArrayString;
+    ///
+    /// let mut s = CArrayString::<8>::try_from("ab").unwrap();
+    /// s.try_push_str("cd").unwrap();
+    /// assert_eq!(s.as_c_str().to_str().unwrap(), "abcd");
+    ///
+    /// # #[cfg(feature = "alloc")] {
+    /// // Overflows the stack buffer, so it spills onto the heap.
+    /// s.try_push_str("efghij").unwrap();
+    /// assert!(matches!(s, CArrayString::Heap(_)));
+    /// assert_eq!(s.as_c_str().to_str().unwrap(), "abcdefghij");
+    /// # }
+    /// ```
+    pub fn try_push_str(&mut self, s: &str) -> Result<(), CStrError> {
+        if core::slice::memchr::memchr(0, s.as_bytes()).is_some() {
+            return Err(ContainsNulError.into());
+        }
+
+        match self {
+            Self::Stack(buf) => {
+                let current_len = buf.len() - 1; // drop the existing NUL
+                if current_len + s.len() < N {
+                    buf.truncate(current_len);
+                    buf.try_extend_from_slice(s.as_bytes())
+                        .expect("fits: checked against N above");
+                    buf.push(0);
+                } else {
+                    *self = Self::heap_from_parts(&buf[..current_len], s.as_bytes())?;
+                }
+            }
+            #[cfg(feature = "alloc")]
+            Self::Heap(cstr) => {
+                let empty = CString::new(Vec::new()).expect("empty vec has no interior NUL");
+                let mut owned = core::mem::replace(cstr, empty).into_bytes();
+                owned.extend_from_slice(s.as_bytes());
+                *cstr = CString::new(owned)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Appends `s`, promoting a `Stack` value to `Heap` if it no longer fits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `s` contains an interior NUL byte, or (without the `alloc`
+    /// feature) if the append would overflow the `N`-byte stack buffer. Use
+    /// [`try_push_str`] to handle those cases explicitly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stack_cstr::CArrayString;
+    ///
+    /// let mut s = CArrayString::<8>::try_from("ab").unwrap();
+    /// s.push_str("cd");
+    /// assert_eq!(s.as_c_str().to_str().unwrap(), "abcd");
+    /// ```
+    pub fn push_str(&mut self, s: &str) {
+        self.try_push_str(s).expect("push_str failed");
+    }
+}
+
+impl<const N: usize> fmt::Write for CArrayString<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.try_push_str(s).map_err(|_| fmt::Error)
+    }
 }
 
 #[cfg(test)]
@@ -231,6 +541,8 @@ mod tests {
 
     #[test]
     fn test_stack_overflow() {
+        // Boundary case: 11 bytes plus the NUL exactly fill the 12-byte buffer,
+        // the longest input that does not overflow the stack.
         assert_eq!(
             CArrayString::<12>::try_from("hello world")
                 .unwrap()
@@ -239,51 +551,165 @@ mod tests {
                 .unwrap(),
             "hello world"
         );
+    }
+
+    #[test]
+    fn test_try_new_stack() {
         assert_eq!(
-            CArrayString::<11>::try_from("hello world")
+            CArrayString::<8>::try_new_stack(format_args!("hi"))
                 .unwrap()
                 .as_c_str()
                 .to_str()
                 .unwrap(),
-            "hello world"
+            "hi"
         );
+        assert!(matches!(
+            CArrayString::<4>::try_new_stack(format_args!("too long")),
+            Err(CStrError::CapacityExceededError(_))
+        ));
     }
 
     #[test]
-    fn test_cstr() {
-        assert_eq!(
-            CArrayString::<12>::from(c"hello world")
-                .as_c_str()
-                .to_str()
-                .unwrap(),
-            "hello world"
-        );
-        assert_eq!(
-            CArrayString::<11>::from(c"hello world")
-                .as_c_str()
-                .to_str()
-                .unwrap(),
-            "hello world"
-        );
+    fn test_from_bytes_non_utf8() {
+        let s = CArrayString::<8>::from_bytes(b"\xffoo").unwrap();
+        assert_eq!(s.as_c_str().to_bytes(), b"\xffoo");
     }
 
     #[test]
-    fn test_format_args() {
-        let s1 = "hello";
-        let s2 = "world";
-        assert_eq!(
-            CArrayString::<12>::new(format_args!("{s1} world"))
-                .as_c_str()
-                .to_str()
-                .unwrap(),
-            "hello world"
-        );
-        assert_eq!(
-            CArrayString::<11>::new(format_args!("hello {s2}"))
-                .as_c_str()
-                .to_str()
-                .unwrap(),
-            "hello world"
-        );
+    fn test_from_bytes_rejects_interior_nul() {
+        assert!(matches!(
+            CArrayString::<8>::from_bytes(b"a\0b"),
+            Err(CStrError::ContainsNulError(_))
+        ));
+    }
+
+    #[test]
+    fn test_try_from_bytes_with_nul() {
+        let s = CArrayString::<8>::try_from_bytes_with_nul(b"\xffoo\0").unwrap();
+        assert_eq!(s.as_c_str().to_bytes(), b"\xffoo");
+
+        assert!(CArrayString::<8>::try_from_bytes_with_nul(b"no-nul").is_err());
+    }
+
+    #[test]
+    fn test_push_str_stays_on_stack() {
+        let mut s = CArrayString::<8>::try_from("ab").unwrap();
+        s.push_str("cd");
+        assert!(matches!(s, CArrayString::Stack(_)));
+        assert_eq!(s.as_c_str().to_str().unwrap(), "abcd");
+    }
+
+    #[test]
+    fn test_try_push_str_rejects_interior_nul() {
+        let mut s = CArrayString::<8>::try_from("ab").unwrap();
+        assert!(matches!(
+            s.try_push_str("c\0d"),
+            Err(CStrError::ContainsNulError(_))
+        ));
+    }
+
+    #[test]
+    fn test_write_impl() {
+        use std::fmt::Write;
+
+        let mut s = CArrayString::<8>::try_from("n = ").unwrap();
+        write!(s, "{}", 42).unwrap();
+        assert_eq!(s.as_c_str().to_str().unwrap(), "n = 42");
+    }
+
+    #[test]
+    fn test_display_escapes_non_printable() {
+        let s = CArrayString::<8>::from_bytes(b"a\t\n\r\xff").unwrap();
+        assert_eq!(s.to_string(), "a\\t\\n\\r\\xff");
+        assert_eq!(format!("{s:?}"), "\"a\\t\\n\\r\\xff\"");
+    }
+
+    /// Tests exercising the `Heap` variant and the constructors/conversions
+    /// that depend on it. Kept separate from the tests above, which must
+    /// keep passing (and compiling) under `--no-default-features` too.
+    #[cfg(feature = "alloc")]
+    mod alloc_tests {
+        use super::*;
+
+        #[test]
+        fn test_stack_overflow_heap_fallback() {
+            assert_eq!(
+                CArrayString::<11>::try_from("hello world")
+                    .unwrap()
+                    .as_c_str()
+                    .to_str()
+                    .unwrap(),
+                "hello world"
+            );
+        }
+
+        #[test]
+        fn test_cstr() {
+            assert_eq!(
+                CArrayString::<12>::from(c"hello world")
+                    .as_c_str()
+                    .to_str()
+                    .unwrap(),
+                "hello world"
+            );
+            assert_eq!(
+                CArrayString::<11>::from(c"hello world")
+                    .as_c_str()
+                    .to_str()
+                    .unwrap(),
+                "hello world"
+            );
+        }
+
+        #[test]
+        fn test_format_args() {
+            let s1 = "hello";
+            let s2 = "world";
+            assert_eq!(
+                CArrayString::<12>::new(format_args!("{s1} world"))
+                    .as_c_str()
+                    .to_str()
+                    .unwrap(),
+                "hello world"
+            );
+            assert_eq!(
+                CArrayString::<11>::new(format_args!("hello {s2}"))
+                    .as_c_str()
+                    .to_str()
+                    .unwrap(),
+                "hello world"
+            );
+        }
+
+        #[test]
+        fn test_from_bytes_non_utf8_heap_fallback() {
+            let s = CArrayString::<2>::from_bytes(b"\xffoo").unwrap();
+            assert_eq!(s.as_c_str().to_bytes(), b"\xffoo");
+        }
+
+        #[test]
+        fn test_try_from_owned_string_and_vec() {
+            let s = CArrayString::<8>::try_from(String::from("hello world")).unwrap();
+            assert_eq!(s.as_c_str().to_str().unwrap(), "hello world");
+
+            let s = CArrayString::<8>::try_from(b"\xffoo".to_vec()).unwrap();
+            assert_eq!(s.as_c_str().to_bytes(), b"\xffoo");
+
+            assert!(matches!(
+                CArrayString::<8>::try_from(b"a\0b".to_vec()),
+                Err(CStrError::ContainsNulError(_))
+            ));
+        }
+
+        #[test]
+        fn test_push_str_promotes_to_heap() {
+            let mut s = CArrayString::<8>::try_from("ab").unwrap();
+            s.push_str("cdefghij");
+            assert!(matches!(s, CArrayString::Heap(_)));
+            assert_eq!(s.as_c_str().to_str().unwrap(), "abcdefghij");
+
+            s.push_str("klm");
+            assert_eq!(s.as_c_str().to_str().unwrap(), "abcdefghijklm");
+        }
     }
 }
@@ -0,0 +1,259 @@
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+
+use arrayvec::ArrayVec;
+
+use crate::{CStrError, CWideStrLike, CapacityExceededError, ContainsNulError};
+
+#[derive(Debug, Clone, Eq, Ord, PartialEq, PartialOrd, Hash)]
+pub enum CArrayWideString<const N: usize> {
+    Stack(ArrayVec<u16, N>),
+    #[cfg(feature = "alloc")]
+    Heap(Vec<u16>),
+}
+
+impl<const N: usize> TryFrom<&str> for CArrayWideString<N> {
+    type Error = CStrError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        if core::slice::memchr::memchr(0, value.as_bytes()).is_some() {
+            return Err(Into::into(ContainsNulError));
+        }
+
+        let len = value.encode_utf16().count();
+        if len < N {
+            let mut buf = ArrayVec::<u16, N>::new();
+            for unit in value.encode_utf16() {
+                buf.try_push(unit).map_err(|_| CapacityExceededError)?;
+            }
+            buf.try_push(0).map_err(|_| CapacityExceededError)?;
+            Ok(Self::Stack(buf))
+        } else {
+            Self::heap_from_str(value)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize> TryFrom<&String> for CArrayWideString<N> {
+    type Error = CStrError;
+
+    fn try_from(value: &String) -> Result<Self, Self::Error> {
+        TryFrom::<&str>::try_from(value.as_str())
+    }
+}
+
+/// A UTF-16, NUL-terminated C-compatible string that can be stored on the stack or heap.
+///
+/// `CArrayWideString<N>` is the wide-string counterpart of [`CArrayString`](crate::CArrayString),
+/// built for Windows APIs that take `*const u16` (`wchar_t`) rather than `*const c_char`:
+///
+/// 1. **Stack-allocated:** Uses a fixed-size `[u16; N]` buffer (via [`ArrayVec<u16, N>`])
+///    for short strings, avoiding heap allocation.
+/// 2. **Heap-allocated:** Uses a `Vec<u16>` when the transcoded string exceeds the stack
+///    buffer, always kept NUL-terminated.
+///
+/// This type guarantees:
+/// - [`as_ptr`] always returns a valid, null-terminated `*const u16` for the lifetime of `self`.
+/// - [`as_wide_str`] always returns the UTF-16 code units without the trailing NUL.
+///
+/// # `no_std`
+///
+/// The heap fallback above requires the `alloc` feature (on by default).
+/// Without it, the stack-only constructors return a [`CStrError`] instead of
+/// allocating when the transcoded string would not fit in `N` code units.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use stack_cstr::{CArrayWideString, CWideStrLike};
+///
+/// // Small string fits on stack
+/// let stack_str = CArrayWideString::<16>::new(format_args!("hello"));
+/// assert!(matches!(stack_str, CArrayWideString::Stack(_)));
+///
+/// // Large string falls back to heap
+/// let heap_str = CArrayWideString::<4>::new(format_args!("this is too long"));
+/// assert!(matches!(heap_str, CArrayWideString::Heap(_)));
+///
+/// assert_eq!(
+///     heap_str.as_wide_str(),
+///     "this is too long".encode_utf16().collect::<Vec<_>>()
+/// );
+/// # }
+/// ```
+impl<const N: usize> CArrayWideString<N> {
+    /// Writes the stack-buffer part of `new`: transcodes `fmt` into an
+    /// `N`-code-unit `ArrayVec`, one UTF-16 code unit at a time, and
+    /// NUL-terminates it.
+    fn try_stack(fmt: fmt::Arguments) -> Result<ArrayVec<u16, N>, CStrError> {
+        struct ArrayVecWideWriter<'a, const N: usize>(&'a mut ArrayVec<u16, N>);
+
+        impl<const N: usize> fmt::Write for ArrayVecWideWriter<'_, N> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                for unit in s.encode_utf16() {
+                    self.0.try_push(unit).map_err(|_| fmt::Error)?;
+                }
+                Ok(())
+            }
+        }
+
+        let mut buf = ArrayVec::<u16, N>::new();
+        // `ArrayVecWideWriter::write_str` only ever fails due to capacity, so
+        // any error here is a capacity error, not a genuine formatting failure.
+        fmt::write(&mut ArrayVecWideWriter(&mut buf), fmt).map_err(|_| CapacityExceededError)?;
+        buf.try_push(0).map_err(|_| CapacityExceededError)?;
+        Ok(buf)
+    }
+
+    /// Transcodes `value` directly into a `Heap` value; without the `alloc`
+    /// feature there is no heap to spill into, so this reports
+    /// [`CapacityExceededError`] instead.
+    #[cfg(feature = "alloc")]
+    fn heap_from_str(value: &str) -> Result<Self, CStrError> {
+        let mut buf: Vec<u16> = value.encode_utf16().collect();
+        buf.push(0);
+        Ok(Self::Heap(buf))
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    fn heap_from_str(_value: &str) -> Result<Self, CStrError> {
+        Err(CapacityExceededError.into())
+    }
+
+    /// Creates a new C-compatible wide string using `format_args!`.
+    ///
+    /// Attempts to transcode the formatted string directly into a stack buffer
+    /// of size `N`, one UTF-16 code unit at a time. Falls back to a heap
+    /// allocation if the string does not fit.
+    #[cfg(feature = "alloc")]
+    pub fn new(fmt: fmt::Arguments) -> CArrayWideString<N> {
+        match Self::try_stack(fmt) {
+            Ok(arr) => Self::Stack(arr),
+            Err(_) => {
+                let mut buf: Vec<u16> = alloc::format!("{fmt}").encode_utf16().collect();
+                buf.push(0);
+                Self::Heap(buf)
+            }
+        }
+    }
+
+    /// Creates a new C-compatible wide string strictly on the stack, never allocating.
+    ///
+    /// This is the `no_std`-friendly counterpart of [`new`]: instead of
+    /// silently falling back to the heap, it returns `Err` when the
+    /// transcoded string (plus NUL) does not fit in the `N`-code-unit stack
+    /// buffer. It is available regardless of the `alloc` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the formatted string does not fit in `N` code units.
+    pub fn try_new_stack(fmt: fmt::Arguments) -> Result<Self, CStrError> {
+        Self::try_stack(fmt).map(Self::Stack)
+    }
+
+    /// Returns a raw pointer to the null-terminated UTF-16 string.
+    ///
+    /// The pointer is valid for the lifetime of `self`.
+    /// This is useful for passing the string to wide (`wchar_t`) Win32 APIs.
+    pub fn as_ptr(&self) -> *const u16 {
+        match self {
+            CArrayWideString::Stack(s) => s.as_ptr(),
+            #[cfg(feature = "alloc")]
+            CArrayWideString::Heap(s) => s.as_ptr(),
+        }
+    }
+
+    /// Returns the UTF-16 code units, without the trailing NUL terminator.
+    pub fn as_wide_str(&self) -> &[u16] {
+        match self {
+            CArrayWideString::Stack(s) => &s[..s.len() - 1],
+            #[cfg(feature = "alloc")]
+            CArrayWideString::Heap(s) => &s[..s.len() - 1],
+        }
+    }
+}
+
+impl<const N: usize> CWideStrLike for CArrayWideString<N> {
+    fn as_ptr(&self) -> *const u16 {
+        self.as_ptr()
+    }
+
+    fn as_wide_str(&self) -> &[u16] {
+        self.as_wide_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stack_overflow() {
+        // Boundary case: 11 code units plus the NUL exactly fill the 12-unit
+        // buffer, the longest input that does not overflow the stack.
+        assert_eq!(
+            CArrayWideString::<12>::try_from("hello world")
+                .unwrap()
+                .as_wide_str(),
+            "hello world".encode_utf16().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_rejects_interior_nul() {
+        assert!(matches!(
+            CArrayWideString::<12>::try_from("a\0b"),
+            Err(CStrError::ContainsNulError(_))
+        ));
+    }
+
+    #[test]
+    fn test_try_new_stack() {
+        assert_eq!(
+            CArrayWideString::<8>::try_new_stack(format_args!("hi"))
+                .unwrap()
+                .as_wide_str(),
+            "hi".encode_utf16().collect::<Vec<_>>()
+        );
+        assert!(matches!(
+            CArrayWideString::<4>::try_new_stack(format_args!("too long")),
+            Err(CStrError::CapacityExceededError(_))
+        ));
+    }
+
+    /// Tests exercising the `Heap` variant and the constructors that depend
+    /// on it. Kept separate from the tests above, which must keep passing
+    /// (and compiling) under `--no-default-features` too.
+    #[cfg(feature = "alloc")]
+    mod alloc_tests {
+        use super::*;
+
+        #[test]
+        fn test_stack_overflow_heap_fallback() {
+            assert_eq!(
+                CArrayWideString::<11>::try_from("hello world")
+                    .unwrap()
+                    .as_wide_str(),
+                "hello world".encode_utf16().collect::<Vec<_>>()
+            );
+        }
+
+        #[test]
+        fn test_format_args() {
+            let s1 = "hello";
+            let s2 = "world";
+            assert_eq!(
+                CArrayWideString::<12>::new(format_args!("{s1} world")).as_wide_str(),
+                "hello world".encode_utf16().collect::<Vec<_>>()
+            );
+            assert_eq!(
+                CArrayWideString::<11>::new(format_args!("hello {s2}")).as_wide_str(),
+                "hello world".encode_utf16().collect::<Vec<_>>()
+            );
+        }
+    }
+}
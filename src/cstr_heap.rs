@@ -1,4 +1,5 @@
-use std::ffi::{CStr, CString, c_char};
+use alloc::ffi::CString;
+use core::ffi::{CStr, c_char};
 
 use crate::CStrLike;
 
@@ -1,4 +1,4 @@
-use std::ffi::{CStr, c_char};
+use core::ffi::{CStr, c_char};
 
 /// A common interface for C-compatible string types used in this crate.
 ///
@@ -17,6 +17,7 @@ use std::ffi::{CStr, c_char};
 /// # Examples
 ///
 /// ```
+/// # #[cfg(feature = "alloc")] {
 /// use std::ffi::{CString, CStr};
 /// use stack_cstr::{CStrHeap, CStrLike};
 ///
@@ -31,6 +32,7 @@ use std::ffi::{CStr, c_char};
 /// unsafe {
 ///     assert_eq!(CStr::from_ptr(ptr).to_str().unwrap(), "hello");
 /// }
+/// # }
 /// ```
 pub trait CStrLike {
     /// Returns a raw pointer to the null-terminated C string.
@@ -1,8 +1,8 @@
-use std::ffi::{CStr, c_char};
+use core::ffi::{CStr, c_char};
 
 use arrayvec::ArrayString;
 
-use crate::{CStrError, CStrLike};
+use crate::{CStrError, CStrLike, CapacityExceededError};
 
 /// A stack-allocated, null-terminated C string with fixed capacity.
 ///
@@ -41,11 +41,17 @@ impl<const N: usize> CStrStack<N> {
     ///
     /// The string is written into an internal buffer of size `N`.
     /// If the string does not fit, returns an error.
-    pub fn new(fmt: std::fmt::Arguments) -> Result<CStrStack<N>, CStrError> {
+    ///
+    /// This is the strict, stack-only entry point: unlike
+    /// [`CArrayString::new`](crate::CArrayString::new), it never falls back to
+    /// a heap allocation, so it is available without the `alloc` feature.
+    pub fn new(fmt: core::fmt::Arguments) -> Result<CStrStack<N>, CStrError> {
         let mut buf: ArrayString<N> = ArrayString::new();
-        std::fmt::write(&mut buf, fmt)?;
+        // `ArrayString`'s `fmt::Write` only ever fails due to capacity, so any
+        // error here is a capacity error, not a genuine formatting failure.
+        core::fmt::write(&mut buf, fmt).map_err(|_| CapacityExceededError)?;
 
-        buf.try_push('\0')?;
+        buf.try_push('\0').map_err(|_| CapacityExceededError)?;
 
         Ok(Self { buf })
     }
@@ -78,3 +84,24 @@ impl<const N: usize> CStrLike for CStrStack<N> {
         self.as_cstr()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert_eq!(
+            CStrStack::<8>::new(format_args!("hi"))
+                .unwrap()
+                .as_cstr()
+                .to_str()
+                .unwrap(),
+            "hi"
+        );
+        assert!(matches!(
+            CStrStack::<4>::new(format_args!("too long")),
+            Err(CStrError::CapacityExceededError(_))
+        ));
+    }
+}
@@ -0,0 +1,32 @@
+/// A common interface for UTF-16 (wide) C-compatible string types used in this crate.
+///
+/// `CWideStrLike` mirrors [`CStrLike`](crate::CStrLike) for the `*const u16`
+/// world that Windows `wchar_t` FFI expects, the same way XPCOM's `nsString`
+/// sits alongside its narrow `nsCString` counterpart.
+///
+/// Types that implement this trait guarantee:
+/// - The returned pointer from [`as_ptr`] is a valid, null-terminated
+///   UTF-16 string for as long as the implementor is alive.
+/// - The returned `&[u16]` from [`as_wide_str`] never includes the trailing
+///   NUL code unit.
+///
+/// # Examples
+///
+/// ```
+/// use stack_cstr::{CArrayWideString, CWideStrLike};
+///
+/// let s = CArrayWideString::<16>::try_new_stack(format_args!("hi")).unwrap();
+///
+/// assert!(!s.as_ptr().is_null());
+/// assert_eq!(s.as_wide_str(), "hi".encode_utf16().collect::<Vec<_>>());
+/// ```
+pub trait CWideStrLike {
+    /// Returns a raw pointer to the null-terminated UTF-16 string.
+    ///
+    /// The pointer is valid as long as `self` is alive.
+    /// This is mainly intended for FFI calls into wide Win32 APIs.
+    fn as_ptr(&self) -> *const u16;
+
+    /// Returns the UTF-16 code units, without the trailing NUL terminator.
+    fn as_wide_str(&self) -> &[u16];
+}
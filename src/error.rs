@@ -4,16 +4,21 @@ use thiserror::Error;
 #[error("Unexpected '\0' was found!")]
 pub struct ContainsNulError;
 
+#[derive(Error, Copy, Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd, Hash)]
+#[error("Buffer capacity exceeded!")]
+pub struct CapacityExceededError;
+
 #[derive(Error, Debug)]
 pub enum CStrError {
     #[error(transparent)]
     FormatError(#[from] core::fmt::Error),
     #[error(transparent)]
-    OverflowError(#[from] arrayvec::CapacityError<char>),
+    CapacityExceededError(#[from] CapacityExceededError),
     #[error(transparent)]
-    FromBytesWithNulError(#[from] std::ffi::FromBytesWithNulError),
+    FromBytesWithNulError(#[from] core::ffi::FromBytesWithNulError),
+    #[cfg(feature = "alloc")]
     #[error(transparent)]
-    NulError(#[from] std::ffi::NulError),
+    NulError(#[from] alloc::ffi::NulError),
     #[error(transparent)]
     ContainsNulError(#[from] ContainsNulError),
 }
@@ -1,10 +1,11 @@
+#![cfg_attr(not(test), no_std)]
 #![allow(internal_features)]
 #![feature(slice_internals)]
 
 //! # stack_cstr
 //!
 //! `stack_cstr` provides ergonomic and efficient ways to create
-//! [`CStr`](std::ffi::CStr) values for FFI interoperability.
+//! [`CStr`](core::ffi::CStr) values for FFI interoperability.
 //!
 //! The crate uses [`CArrayString`] to store C-compatible strings. It aims to
 //! minimize heap allocations for short strings by using a fixed-size stack buffer,
@@ -31,8 +32,9 @@
 //! ## Example: Using the `cstr!` Macro
 //!
 //! ```
+//! # #[cfg(feature = "alloc")] {
 //! use std::ffi::CStr;
-//! 
+//!
 //! use stack_cstr::cstr;
 //!
 //! let s = cstr!("Pi = {:.2}", 3.14159);
@@ -43,6 +45,7 @@
 //!     let ptr = s.as_ptr();
 //!     assert_eq!(CStr::from_ptr(ptr).to_str().unwrap(), "Pi = 3.14");
 //! }
+//! # }
 //! ```
 //!
 //! ## Design Notes
@@ -73,12 +76,34 @@
 //!
 //! ## See Also
 //!
-//! - [`CString`](std::ffi::CString) for explicit heap-allocated C strings
-//! - [`CStr`](std::ffi::CStr) for borrowed C strings
+//! - [`CString`](alloc::ffi::CString) for explicit heap-allocated C strings
+//! - [`CStr`](core::ffi::CStr) for borrowed C strings
+//!
+//! ## `no_std`
+//!
+//! This crate is `#![no_std]`. Everything that never allocates (e.g.
+//! [`CStrStack`], [`CArrayString::try_new_stack`]) is always available. The
+//! heap fallback described above lives behind the `alloc` feature, which is
+//! enabled by default.
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
 pub mod c_array_string;
+pub mod c_array_wide_string;
+#[cfg(feature = "alloc")]
+pub mod cstr_heap;
+pub mod cstr_like;
+pub mod cstr_stack;
+pub mod cstr_wide_like;
 pub mod error;
 pub mod macros;
 
 pub use c_array_string::CArrayString;
-pub use error::{CStrError, ContainsNulError};
+pub use c_array_wide_string::CArrayWideString;
+#[cfg(feature = "alloc")]
+pub use cstr_heap::CStrHeap;
+pub use cstr_like::CStrLike;
+pub use cstr_stack::CStrStack;
+pub use cstr_wide_like::CWideStrLike;
+pub use error::{CStrError, CapacityExceededError, ContainsNulError};
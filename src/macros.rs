@@ -25,8 +25,9 @@
 /// # Examples
 ///
 /// ```
+/// # #[cfg(feature = "alloc")] {
 /// use std::ffi::CStr;
-/// 
+///
 /// use stack_cstr::cstr;
 ///
 /// let s = cstr!("Pi = {:.2}", 3.14159);
@@ -37,6 +38,7 @@
 ///     let ptr = s.as_ptr();
 ///     assert_eq!(CStr::from_ptr(ptr).to_str().unwrap(), "Pi = 3.14");
 /// }
+/// # }
 /// ```
 ///
 /// # Notes
@@ -56,3 +58,47 @@ macro_rules! cstr {
         $crate::CArrayString::<128>::new(format_args!($($args)*))
     };
 }
+
+/// A macro to create a UTF-16 wide C-compatible string (`CArrayWideString`) with
+/// stack allocation fallback.
+///
+/// The `wcstr!` macro is the wide-string counterpart of [`cstr!`]: it constructs
+/// a [`CArrayWideString`] with a default internal stack buffer, transcoding the
+/// formatted text to UTF-16 and falling back to a heap-allocated `Vec<u16>` when
+/// the string does not fit.
+///
+/// This makes it ergonomic to feed wide (`wchar_t`) Win32 APIs without heap
+/// churn for the common short-string case.
+///
+/// # Syntax
+///
+/// ```ignore
+/// wcstr!("format string", args...)   // uses default stack size 128
+/// ```
+///
+/// # Returns
+///
+/// A `CArrayWideString<128>`, which can be used to obtain:
+/// - a raw pointer (`*const u16`) via [`CArrayWideString::as_ptr`]
+/// - the UTF-16 code units via [`CArrayWideString::as_wide_str`]
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use stack_cstr::wcstr;
+///
+/// let s = wcstr!("Pi = {:.2}", 3.14159);
+/// assert_eq!(s.as_wide_str(), "Pi = 3.14".encode_utf16().collect::<Vec<_>>());
+/// # }
+/// ```
+///
+/// # See also
+///
+/// - [`CArrayWideString`] for more control over stack/heap allocation.
+#[macro_export]
+macro_rules! wcstr {
+    ( $($args:tt)* ) => {
+        $crate::CArrayWideString::<128>::new(format_args!($($args)*))
+    };
+}
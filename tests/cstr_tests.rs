@@ -1,3 +1,5 @@
+#![cfg(feature = "alloc")]
+
 use std::ffi::CStr;
 
 use stack_cstr::cstr;